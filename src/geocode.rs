@@ -0,0 +1,94 @@
+use anyhow::{anyhow, Result};
+use reqwest::blocking::Client;
+use serde::Deserialize;
+
+use crate::report::Coordinates;
+
+const OWM_GEOCODE_DIRECT_ENDPOINT: &str = "https://api.openweathermap.org/geo/1.0/direct";
+const OWM_GEOCODE_ZIP_ENDPOINT: &str = "https://api.openweathermap.org/geo/1.0/zip";
+const OWM_WEATHER_ENDPOINT: &str = "https://api.openweathermap.org/data/2.5/weather";
+
+/// A resolved, human-readable location alongside its coordinates.
+#[derive(Debug, Clone)]
+pub struct GeocodeResult {
+  pub coords: Coordinates,
+  pub name: String
+}
+
+#[derive(Debug, Deserialize)]
+struct DirectGeocodeEntry {
+  name: String,
+  lat: f32,
+  lon: f32,
+  country: String
+}
+
+/// resolves a location by name (e.g. "Seattle" or "Seattle,US") via OWM's
+/// direct geocoding API.
+pub fn resolve_city(client: &Client, city: &str, api_key: &str) -> Result<GeocodeResult> {
+  let entries = client.get(OWM_GEOCODE_DIRECT_ENDPOINT)
+    .query(&[("q", city), ("limit", "1"), ("appid", api_key)])
+    .send()?
+    .error_for_status()?
+    .json::<Vec<DirectGeocodeEntry>>()?;
+
+  let entry = entries.into_iter().next()
+    .ok_or_else(|| anyhow!("no results for city '{}'", city))?;
+
+  Ok(GeocodeResult {
+    coords: Coordinates { lat: entry.lat, lon: entry.lon },
+    name: format!("{},{}", entry.name, entry.country)
+  })
+}
+
+#[derive(Debug, Deserialize)]
+struct ZipGeocodeResponse {
+  name: String,
+  lat: f32,
+  lon: f32,
+  country: String
+}
+
+/// resolves a location by zip/postal code and ISO 3166 country code via
+/// OWM's zip geocoding API.
+pub fn resolve_zip(client: &Client, zip: &str, country: &str, api_key: &str) -> Result<GeocodeResult> {
+  let query = format!("{},{}", zip, country);
+
+  let entry = client.get(OWM_GEOCODE_ZIP_ENDPOINT)
+    .query(&[("zip", query.as_str()), ("appid", api_key)])
+    .send()?
+    .error_for_status()?
+    .json::<ZipGeocodeResponse>()?;
+
+  Ok(GeocodeResult {
+    coords: Coordinates { lat: entry.lat, lon: entry.lon },
+    name: format!("{},{}", entry.name, entry.country)
+  })
+}
+
+#[derive(Debug, Deserialize)]
+struct CityIdWeatherResponse {
+  name: String,
+  sys: CityIdWeatherSys,
+  coord: Coordinates
+}
+
+#[derive(Debug, Deserialize)]
+struct CityIdWeatherSys {
+  country: String
+}
+
+/// resolves a location by OWM's legacy numeric city id, by requesting its
+/// current weather once and keeping only the name/coordinates.
+pub fn resolve_city_id(client: &Client, city_id: u64, api_key: &str) -> Result<GeocodeResult> {
+  let response = client.get(OWM_WEATHER_ENDPOINT)
+    .query(&[("id", city_id.to_string().as_str()), ("appid", api_key)])
+    .send()?
+    .error_for_status()?
+    .json::<CityIdWeatherResponse>()?;
+
+  Ok(GeocodeResult {
+    coords: response.coord,
+    name: format!("{},{}", response.name, response.sys.country)
+  })
+}
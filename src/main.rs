@@ -1,9 +1,14 @@
 #[macro_use] extern crate log;
 
+mod report;
+mod providers;
+mod geocode;
+
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::fmt;
 
 use anyhow::{anyhow, Result};
@@ -14,18 +19,29 @@ use serde::{Serialize, Deserialize};
 use serde_json::json;
 use simple_prometheus_exporter::{Exporter, export};
 use warp::Filter;
+use futures::StreamExt;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+
+use report::{Coordinates, Report, ReportWind};
+use providers::{WeatherProvider, ProviderKind};
+
+/// the number of events buffered for each `/events` subscriber; a subscriber
+/// that falls further behind than this misses the oldest events
+const EVENT_CHANNEL_CAPACITY: usize = 16;
 
-const OWM_API_ENDPOINT: &str = "https://api.openweathermap.org/data/2.5/weather";
+const OWM_FORECAST_API_ENDPOINT: &str = "https://api.openweathermap.org/data/2.5/forecast";
+const AUTOLOCATE_API_ENDPOINT: &str = "https://ipapi.co/json/";
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
-enum Units {
+pub(crate) enum Units {
   Kelvin,
   Imperial,
   Metric,
 }
 
 impl Units {
-  fn api_param(&self) -> Option<&'static str> {
+  pub(crate) fn api_param(&self) -> Option<&'static str> {
     match self {
       Units::Kelvin => None,
       Units::Metric => Some("metric"),
@@ -79,16 +95,61 @@ impl FromStr for Units {
 #[derive(Debug, Clone, StructOpt)]
 #[structopt(name = "openweathermap-exporter")]
 struct Options {
-  /// comma-separated lat/lon coords, e.g. 123.0,456.0
-  coords: Coordinates,
+  /// one or more monitored locations, each as lat,lon or lat,lon@label
+  /// (e.g. `47.6,-122.3@seattle`); multiple locations may be passed as
+  /// separate arguments or combined into one `;`-separated argument; may be
+  /// omitted entirely if `--autolocate` or `--city`/`--city-id`/`--zip` is set
+  locations: Vec<String>,
+
+  /// resolve an additional location by city name via OWM geocoding, e.g.
+  /// "Seattle" or "Seattle,US"; requires --api-key
+  #[structopt(long)]
+  city: Option<String>,
+
+  /// resolve an additional location by OWM's numeric city id; requires
+  /// --api-key; ignored if --city is set
+  #[structopt(long)]
+  city_id: Option<u64>,
+
+  /// resolve an additional location by zip/postal code via OWM geocoding,
+  /// paired with --country; requires --api-key; ignored if --city or
+  /// --city-id is set
+  #[structopt(long)]
+  zip: Option<String>,
+
+  /// ISO 3166 country code used with --zip, e.g. "us"
+  #[structopt(long, default_value = "us")]
+  country: String,
+
+  /// if set and no locations are given (or the given location is used only
+  /// as a fallback), resolves the host's approximate lat/lon via IP
+  /// geolocation instead of requiring coordinates up front
+  #[structopt(long)]
+  autolocate: bool,
+
+  /// how often to re-resolve the autolocated position, in seconds; useful
+  /// for roaming hosts. if 0 (the default), the position is resolved once
+  /// at startup and never refreshed
+  #[structopt(long, default_value = "0", env = "OWM_AUTOLOCATE_INTERVAL")]
+  autolocate_interval: f32,
 
   /// unit type, one of: kelvin, metric, imperial
   #[structopt(long, short, default_value = "kelvin", env = "OWM_UNITS")]
   units: Units,
 
-  /// openweathermap api key
+  /// weather data backend, one of: openweathermap, open-meteo. open-meteo
+  /// requires no api key
+  #[structopt(long, default_value = "openweathermap", env = "OWM_PROVIDER")]
+  provider: ProviderKind,
+
+  /// openweathermap api key, required when using the openweathermap provider
   #[structopt(long, short, env = "OWM_API_KEY")]
-  api_key: String,
+  api_key: Option<String>,
+
+  /// language code for condition descriptions (e.g. "en", "de", "zh_cn");
+  /// only supported by the openweathermap provider
+  #[structopt(long, env = "OWM_LANG")]
+  lang: Option<String>,
 
   /// refresh interval in seconds
   #[structopt(long, short, default_value = "120.0", env = "OWM_INTERVAL")]
@@ -102,126 +163,247 @@ struct Options {
   #[structopt(long, short, default_value = "8081", env = "OWM_PORT")]
   port: u16,
 
-  /// if set, adds a `location=$location` label to all exported metrics
+  /// if set, adds a `location=$location` label to the exported metrics for
+  /// the single location given (ignored if a location already specifies its
+  /// own `@label`, or if multiple locations are monitored); a location
+  /// resolved via --city/--city-id/--zip always takes this label when set
   #[structopt(long, short)]
-  location: Option<String>
+  location: Option<String>,
+
+  /// if set, also fetches the 5-day/3-hour forecast and exports entries
+  /// falling within this many hours from now
+  #[structopt(long)]
+  forecast_hours: Option<u32>
+}
+
+/// A single monitored location, parsed from a `lat,lon` or `lat,lon@label`
+/// CLI argument.
+#[derive(Debug, Clone)]
+struct MonitoredLocation {
+  coords: Coordinates,
+  label: Option<String>
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct Coordinates {
-  lat: f32,
-  lon: f32
+impl MonitoredLocation {
+  /// the label used for this location's `location=` metric label: its own
+  /// `@label`, falling back to `default_label` (only meaningful when there
+  /// is a single monitored location), falling back to its coordinates.
+  fn key(&self, default_label: &Option<String>) -> String {
+    self.label.clone()
+      .or_else(|| default_label.clone())
+      .unwrap_or_else(|| format!("{},{}", self.coords.lat, self.coords.lon))
+  }
 }
 
-impl FromStr for Coordinates {
+impl FromStr for MonitoredLocation {
   type Err = anyhow::Error;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    let mut iter = s.splitn(2, ',');
-    let lat = iter.next()
-      .and_then(|s| s.parse::<f32>().ok())
-      .ok_or_else(|| anyhow!("invalid lat"))?;
-    let lon = iter.next()
-      .and_then(|s| s.parse::<f32>().ok())
-      .ok_or_else(|| anyhow!("invalid lon"))?;
-
-    Ok(Coordinates { lat, lon })
+    let (coords, label) = match s.split_once('@') {
+      Some((coords, label)) => (coords, Some(label.to_string())),
+      None => (s, None)
+    };
+
+    Ok(MonitoredLocation { coords: coords.parse()?, label })
   }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ReportCondition {
-  id: u32,
-  main: String,
-  description: String,
-  icon: String
+/// parses the raw positional `locations` arguments, splitting any `;`-separated
+/// entries into individual locations.
+fn parse_locations(raw: &[String]) -> Result<Vec<MonitoredLocation>> {
+  raw.iter()
+    .flat_map(|s| s.split(';'))
+    .map(|s| s.parse())
+    .collect()
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ReportMain {
-  temp: f32,
-  feels_like: f32,
-  temp_min: f32,
-  temp_max: f32,
-  pressure: f32,
-  humidity: f32
+#[derive(Debug, Deserialize)]
+struct AutolocateResponse {
+  latitude: f32,
+  longitude: f32
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ReportWind {
-  pub speed: f32,
-  pub deg: u32
+/// resolves the host's approximate coordinates from a keyless IP
+/// geolocation service.
+fn resolve_autolocation(client: &Client) -> Result<Coordinates> {
+  let response = client.get(AUTOLOCATE_API_ENDPOINT)
+    .send()?
+    .error_for_status()?
+    .json::<AutolocateResponse>()?;
+
+  Ok(Coordinates { lat: response.latitude, lon: response.longitude })
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
-pub struct ReportRain {
-  pub volume_1h: Option<f32>,
-  pub volume_3h: Option<f32>
+/// repeatedly resolves the host's coordinates via IP geolocation, updating
+/// `coords_lock` on success. falls back to `fallback` only if resolution
+/// fails before any successful resolution. if `interval` is <= 0, resolves
+/// once and exits instead of looping.
+fn autolocate_thread(coords_lock: Arc<RwLock<Option<Coordinates>>>, fallback: Option<Coordinates>, interval: f32) {
+  thread::spawn(move || {
+    let client = Client::new();
+
+    loop {
+      match resolve_autolocation(&client) {
+        Ok(coords) => {
+          info!("autolocate resolved: {:?}", coords);
+          *coords_lock.write().unwrap() = Some(coords);
+        },
+        Err(e) => {
+          error!("autolocate error: {:?}", e);
+          if coords_lock.read().unwrap().is_none() {
+            if let Some(fallback) = &fallback {
+              *coords_lock.write().unwrap() = Some(fallback.clone());
+            }
+          }
+        }
+      }
+
+      if interval <= 0.0 {
+        break;
+      }
+
+      thread::sleep(Duration::from_secs_f32(interval));
+    }
+  });
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
-pub struct ReportSnow {
-  pub volume_1h: Option<f32>,
-  pub volume_3h: Option<f32>
+#[derive(Debug, Serialize, Deserialize)]
+struct ForecastApiMain {
+  temp: f32
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-pub struct ReportClouds {
-  all: u32
+struct ForecastApiEntry {
+  dt: i64,
+  main: ForecastApiMain,
+  #[serde(default)]
+  pop: f32,
+  wind: ReportWind
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-struct Report {
-  coord: Coordinates,
-  weather: Vec<ReportCondition>,
-  main: ReportMain,
+struct ForecastApiResponse {
+  list: Vec<ForecastApiEntry>
+}
 
-  wind: ReportWind,
+/// A single forecast entry, resolved to an `offset_hours` from now at fetch
+/// time so `export_report` doesn't need to know the current time.
+#[derive(Debug, Serialize, Clone)]
+struct ForecastEntry {
+  offset_hours: i64,
+  temp: f32,
+  pop: f32,
+  wind_speed: f32,
+  wind_deg: u32
+}
 
-  #[serde(default)]
-  rain: ReportRain,
+/// fetches the forecast endpoint and keeps only entries within
+/// `forecast_hours` from now.
+fn fetch_forecast(client: &Client, coords: &Coordinates, api_key: &str, units: &Units, forecast_hours: u32) -> Result<Vec<ForecastEntry>> {
+  let mut query: Vec<(String, String)> = vec![
+    ("lat".into(), coords.lat.to_string()),
+    ("lon".into(), coords.lon.to_string()),
+    ("appid".into(), api_key.to_string())
+  ];
+
+  if let Some(unit) = units.api_param() {
+    query.push(("units".into(), unit.to_string()));
+  }
 
-  #[serde(default)]
-  snow: ReportSnow,
-  clouds: ReportClouds,
+  let response = client.get(OWM_FORECAST_API_ENDPOINT)
+    .query(&query)
+    .send()?
+    .error_for_status()?
+    .json::<ForecastApiResponse>()?;
+
+  let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+
+  Ok(response.list.into_iter()
+    .filter_map(|entry| {
+      let offset_hours = (entry.dt - now) / 3600;
+      if offset_hours < 0 || offset_hours as u32 > forecast_hours {
+        return None;
+      }
 
-  /// visibility in meters (does not honor units param)
-  visibility: Option<usize>
+      Some(ForecastEntry {
+        offset_hours,
+        temp: entry.main.temp,
+        pop: entry.pop,
+        wind_speed: entry.wind.speed,
+        wind_deg: entry.wind.deg
+      })
+    })
+    .collect())
+}
+
+/// The current report alongside any fetched forecast entries.
+#[derive(Debug, Serialize)]
+struct WeatherData {
+  report: Report,
+  #[serde(default)]
+  forecast: Vec<ForecastEntry>
 }
 
 /// Custom Result-Option hybrid to expose errors from the reporting thread
 enum MaybeReport {
-  Ok(Report),
+  Ok(WeatherData),
   Err(Option<u16>),
   None
 }
 
-fn report_thread(report_lock: Arc<RwLock<MaybeReport>>, opts: Options) {
-  thread::spawn(move || {
-    let client = Client::new();
-
-    let mut query: Vec<(String, String)> = vec![
-      ("lat".into(), opts.coords.lat.to_string()),
-      ("lon".into(), opts.coords.lon.to_string()),
-      ("appid".into(), opts.api_key)
-    ];
+/// the subset of a `Report` compared between fetches to decide whether a
+/// weather transition is significant enough to broadcast on `/events`
+#[derive(Debug, PartialEq)]
+struct ReportSignature {
+  condition_id: u32,
+  temp_rounded: i32,
+  wind_deg: u32,
+  wind_speed_rounded: i32,
+  precipitation: bool
+}
 
-    if let Some(unit) = opts.units.api_param() {
-      query.push(("units".into(), unit.to_string()));
+impl ReportSignature {
+  fn from_report(report: &Report) -> Self {
+    ReportSignature {
+      condition_id: report.weather.first().map(|c| c.id).unwrap_or(0),
+      temp_rounded: report.main.temp.round() as i32,
+      wind_deg: report.wind.deg,
+      wind_speed_rounded: report.wind.speed.round() as i32,
+      precipitation: report.rain.volume_1h.is_some() || report.snow.volume_1h.is_some()
     }
+  }
+}
+
+fn report_thread(
+  report_lock: Arc<RwLock<MaybeReport>>,
+  opts: Options,
+  coords_lock: Arc<RwLock<Option<Coordinates>>>,
+  provider: Arc<dyn WeatherProvider + Send + Sync>,
+  key: String,
+  events: broadcast::Sender<String>
+) {
+  thread::spawn(move || {
+    let client = Client::new();
 
     loop {
-      let response = client.get(OWM_API_ENDPOINT)
-        .query(&query)
-        .send()
-        .and_then(|r| r.error_for_status())
-        .and_then(|r| r.json::<Report>());
-
-      let report = match response {
-        Ok(response) => response,
+      let coords = match coords_lock.read().unwrap().clone() {
+        Some(coords) => coords,
+        None => {
+          // autolocation hasn't resolved a position yet
+          thread::sleep(Duration::from_secs_f32(opts.backoff_interval));
+          continue;
+        }
+      };
+
+      let report = match provider.fetch(&client, &coords, opts.units, opts.lang.as_deref()) {
+        Ok(report) => report,
         Err(e) => {
-          error!("owm api error: {:?}", e);
-          *report_lock.try_write().unwrap() = MaybeReport::Err(e.status().map(|s| s.as_u16()));
+          error!("weather provider error: {:?}", e);
+
+          let status = e.downcast_ref::<reqwest::Error>()
+            .and_then(|e| e.status())
+            .map(|s| s.as_u16());
+          *report_lock.try_write().unwrap() = MaybeReport::Err(status);
 
           thread::sleep(Duration::from_secs_f32(opts.backoff_interval));
           continue;
@@ -231,65 +413,108 @@ fn report_thread(report_lock: Arc<RwLock<MaybeReport>>, opts: Options) {
       info!("report: {:?}", &report.main);
       debug!("full report: {:#?}", &report);
 
-      *report_lock.try_write().unwrap() = MaybeReport::Ok(report);
+      let forecast = match (opts.forecast_hours, opts.provider, &opts.api_key) {
+        (Some(hours), ProviderKind::OpenWeatherMap, Some(api_key)) => {
+          match fetch_forecast(&client, &coords, api_key, &opts.units, hours) {
+            Ok(forecast) => forecast,
+            Err(e) => {
+              error!("owm forecast api error: {:?}", e);
+              Vec::new()
+            }
+          }
+        },
+        _ => Vec::new()
+      };
+
+      let previous_signature = match &*report_lock.read().unwrap() {
+        MaybeReport::Ok(data) => Some(ReportSignature::from_report(&data.report)),
+        MaybeReport::Err(_) | MaybeReport::None => None
+      };
+      let new_signature = ReportSignature::from_report(&report);
+      let changed = previous_signature.as_ref() != Some(&new_signature);
+
+      let data = WeatherData { report, forecast };
+
+      if changed {
+        let payload = json!({ "location": key, "report": &data.report }).to_string();
+        // ignore send errors: no `/events` subscribers is the common case
+        let _ = events.send(payload);
+      }
+
+      *report_lock.try_write().unwrap() = MaybeReport::Ok(data);
 
       thread::sleep(Duration::from_secs_f32(opts.interval));
     }
   });
 }
 
-fn export_report(exporter: &Exporter, report: &MaybeReport, units: &Units) -> String {
+fn export_report(exporter: &Exporter, report: &MaybeReport, units: &Units, location: &str) -> String {
   let mut s = exporter.session();
 
-  let report = match report {
-    MaybeReport::Ok(report) => report,
+  let data = match report {
+    MaybeReport::Ok(data) => data,
     MaybeReport::None => return s.to_string(),
     MaybeReport::Err(code) => {
-      export!(s, "owm_error", 1);
+      export!(s, "owm_error", 1, location = location);
       if let Some(code) = code {
-        export!(s, "owm_error", 1, code = code.to_string());
+        export!(s, "owm_error", 1, location = location, code = code.to_string());
       }
 
       return s.to_string();
     },
   };
 
-  export!(s, "owm_error", 0);
+  let report = &data.report;
+
+  export!(s, "owm_error", 0, location = location);
 
-  export!(s, "owm_temp", report.main.temp, unit = units.units_temp());
-  export!(s, "owm_temp_min", report.main.temp_min, unit = units.units_temp());
-  export!(s, "owm_temp_max", report.main.temp_max, unit = units.units_temp());
-  export!(s, "owm_feels_like", report.main.feels_like, unit = units.units_temp());
-  export!(s, "owm_humidity", report.main.humidity, unit = "percent");
-  export!(s, "owm_pressure", report.main.pressure, unit = units.units_pressure());
+  export!(s, "owm_temp", report.main.temp, location = location, unit = units.units_temp());
+  export!(s, "owm_temp_min", report.main.temp_min, location = location, unit = units.units_temp());
+  export!(s, "owm_temp_max", report.main.temp_max, location = location, unit = units.units_temp());
+  export!(s, "owm_feels_like", report.main.feels_like, location = location, unit = units.units_temp());
+  export!(s, "owm_humidity", report.main.humidity, location = location, unit = "percent");
+  export!(s, "owm_pressure", report.main.pressure, location = location, unit = units.units_pressure());
 
-  export!(s, "owm_clouds_all", report.clouds.all, unit = "percent");
+  export!(s, "owm_clouds_all", report.clouds.all, location = location, unit = "percent");
 
   if let Some(volume) = report.rain.volume_1h {
-    export!(s, "owm_rain_volume", volume, period = "1h", unit = "mm");
+    export!(s, "owm_rain_volume", volume, location = location, period = "1h", unit = "mm");
   }
 
   if let Some(volume) = report.rain.volume_3h {
-    export!(s, "owm_rain_volume", volume, period = "3h", unit = "mm");
+    export!(s, "owm_rain_volume", volume, location = location, period = "3h", unit = "mm");
   }
 
   if let Some(volume) = report.snow.volume_1h {
-    export!(s, "owm_snow_volume", volume, period = "1h", unit = "mm");
+    export!(s, "owm_snow_volume", volume, location = location, period = "1h", unit = "mm");
   }
 
   if let Some(volume) = report.snow.volume_3h {
-    export!(s, "owm_snow_volume", volume, period = "3h", unit = "mm");
+    export!(s, "owm_snow_volume", volume, location = location, period = "3h", unit = "mm");
   }
 
-  export!(s, "owm_wind_direction", report.wind.deg, unit = "degrees");
-  export!(s, "owm_wind_speed", report.wind.speed, unit = units.units_speed());
+  export!(s, "owm_wind_direction", report.wind.deg, location = location, unit = "degrees");
+  export!(s, "owm_wind_speed", report.wind.speed, location = location, unit = units.units_speed());
 
   for condition in &report.weather {
-    export!(s, "owm_condition", 1, kind = &condition.description);
+    export!(s, "owm_condition", 1, location = location, kind = &condition.description);
+
+    // free-text descriptions vary by --lang, so also export the stable
+    // numeric OWM condition code for alerting/dashboards
+    export!(s, "owm_condition_id", 1, location = location, id = condition.id.to_string());
   }
 
   if let Some(visibility) = report.visibility {
-    export!(s, "owm_visiblity", visibility as f64, unit = "meters");
+    export!(s, "owm_visiblity", visibility as f64, location = location, unit = "meters");
+  }
+
+  for entry in &data.forecast {
+    let offset = format!("{}h", entry.offset_hours);
+
+    export!(s, "owm_forecast_temp", entry.temp, location = location, offset = &offset, unit = units.units_temp());
+    export!(s, "owm_forecast_pop", entry.pop, location = location, offset = &offset);
+    export!(s, "owm_forecast_wind_speed", entry.wind_speed, location = location, offset = &offset, unit = units.units_speed());
+    export!(s, "owm_forecast_wind_direction", entry.wind_deg, location = location, offset = &offset, unit = "degrees");
   }
 
   s.to_string()
@@ -302,33 +527,120 @@ async fn main() {
   let opts = Options::from_args();
   let port = opts.port;
 
-  let mut exporter = Exporter::new();
-  if let Some(location) = &opts.location {
-    exporter.add_global_label("location", location);
+  let mut locations = parse_locations(&opts.locations)
+    .unwrap_or_else(|e| panic!("invalid locations: {}", e));
+
+  if opts.city.is_some() || opts.city_id.is_some() || opts.zip.is_some() {
+    let api_key = opts.api_key.as_deref()
+      .unwrap_or_else(|| panic!("--api-key is required to resolve --city/--city-id/--zip"));
+    let client = Client::new();
+
+    let result = if let Some(city) = &opts.city {
+      geocode::resolve_city(&client, city, api_key)
+    } else if let Some(city_id) = opts.city_id {
+      geocode::resolve_city_id(&client, city_id, api_key)
+    } else {
+      let zip = opts.zip.as_ref().unwrap();
+      geocode::resolve_zip(&client, zip, &opts.country, api_key)
+    }.unwrap_or_else(|e| panic!("failed to resolve location: {}", e));
+
+    info!("resolved location '{}' to {:?}", result.name, result.coords);
+
+    locations.push(MonitoredLocation {
+      coords: result.coords,
+      label: Some(opts.location.clone().unwrap_or(result.name))
+    });
   }
 
-  let exporter = Arc::new(exporter);
+  if locations.is_empty() && !opts.autolocate {
+    panic!("at least one location is required, or pass --autolocate, --city, --city-id, or --zip");
+  }
 
-  let latest_report_lock = Arc::new(RwLock::new(MaybeReport::None));
-  report_thread(latest_report_lock.clone(), opts.clone());
+  let provider: Arc<dyn WeatherProvider + Send + Sync> = Arc::from(
+    opts.provider.build(opts.api_key.clone())
+      .unwrap_or_else(|e| panic!("invalid provider configuration: {}", e))
+  );
 
-  let json_lock = Arc::clone(&latest_report_lock);
-  let r_json = warp::path("json").map(move || {
-    match *json_lock.read().unwrap() {
-      MaybeReport::Ok(ref r) => warp::reply::json(&r),
-      MaybeReport::None => warp::reply::json(&json!(null)),
-      MaybeReport::Err(e) => warp::reply::json(&json!({
-        "error": e
-      }))
+  if opts.forecast_hours.is_some() && opts.provider != ProviderKind::OpenWeatherMap {
+    warn!("--forecast-hours is only supported with --provider openweathermap; forecasts will not be fetched");
+  }
+
+  let exporter = Arc::new(Exporter::new());
+
+  // broadcasts a JSON payload whenever any monitored location's report
+  // changes meaningfully; shared by every report_thread and the /events route
+  let (event_tx, _) = broadcast::channel::<String>(EVENT_CHANNEL_CAPACITY);
+
+  let mut report_locks: HashMap<String, Arc<RwLock<MaybeReport>>> = HashMap::new();
+
+  if opts.autolocate {
+    // autolocation only covers a single, roaming position: the first
+    // explicitly given location, if any, is used only as a fallback and as
+    // the metric label; any further locations are not monitored
+    let fallback = locations.first();
+    let key = fallback.map(|l| l.key(&opts.location))
+      .unwrap_or_else(|| opts.location.clone().unwrap_or_else(|| "autolocate".to_string()));
+
+    let coords_lock = Arc::new(RwLock::new(None));
+    autolocate_thread(coords_lock.clone(), fallback.map(|l| l.coords.clone()), opts.autolocate_interval);
+
+    let report_lock = Arc::new(RwLock::new(MaybeReport::None));
+    report_thread(report_lock.clone(), opts.clone(), coords_lock, provider.clone(), key.clone(), event_tx.clone());
+    report_locks.insert(key, report_lock);
+  } else {
+    // --location is only a meaningful default label when there's a single
+    // location to apply it to; with multiple locations it would collide
+    let default_label = if locations.len() == 1 { &opts.location } else { &None };
+
+    for location in &locations {
+      let key = location.key(default_label);
+
+      if report_locks.contains_key(&key) {
+        panic!("duplicate monitored location '{}': give each location a distinct @label", key);
+      }
+
+      let report_lock = Arc::new(RwLock::new(MaybeReport::None));
+      let coords_lock = Arc::new(RwLock::new(Some(location.coords.clone())));
+      report_thread(report_lock.clone(), opts.clone(), coords_lock, provider.clone(), key.clone(), event_tx.clone());
+      report_locks.insert(key, report_lock);
     }
+  }
+
+  let report_locks = Arc::new(report_locks);
+
+  let json_locks = Arc::clone(&report_locks);
+  let r_json = warp::path("json").map(move || {
+    let reports: HashMap<&String, serde_json::Value> = json_locks.iter()
+      .map(|(key, lock)| {
+        let value = match *lock.read().unwrap() {
+          MaybeReport::Ok(ref r) => json!(r),
+          MaybeReport::None => json!(null),
+          MaybeReport::Err(e) => json!({ "error": e })
+        };
+
+        (key, value)
+      })
+      .collect();
+
+    warp::reply::json(&reports)
   });
 
-  let metrics_lock = Arc::clone(&latest_report_lock);
+  let metrics_locks = Arc::clone(&report_locks);
   let r_metrics = warp::path("metrics").map(move || {
-    export_report(&exporter, &*metrics_lock.read().unwrap(), &opts.units)
+    metrics_locks.iter()
+      .map(|(key, lock)| export_report(&exporter, &*lock.read().unwrap(), &opts.units, key))
+      .collect::<String>()
+  });
+
+  let r_events = warp::path("events").map(move || {
+    let stream = BroadcastStream::new(event_tx.subscribe())
+      .filter_map(|msg| async move { msg.ok() })
+      .map(|payload| Ok::<_, warp::Error>(warp::sse::Event::default().data(payload)));
+
+    warp::sse::reply(warp::sse::keep_alive().stream(stream))
   });
 
-  let routes = warp::get().and(r_json).or(r_metrics);
+  let routes = warp::get().and(r_json).or(r_metrics).or(r_events);
   warp::serve(routes).run(([0, 0, 0, 0], port)).await;
 }
 
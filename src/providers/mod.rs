@@ -0,0 +1,63 @@
+mod openweathermap;
+mod open_meteo;
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use reqwest::blocking::Client;
+
+use crate::Units;
+use crate::report::{Coordinates, Report};
+
+pub use openweathermap::OpenWeatherMapProvider;
+pub use open_meteo::OpenMeteoProvider;
+
+/// A pluggable source of current-weather data, normalized into the shared
+/// `Report` shape.
+pub trait WeatherProvider {
+  /// `lang` requests localized `ReportCondition::description` values where
+  /// supported; providers that don't support localization may ignore it.
+  fn fetch(&self, client: &Client, coords: &Coordinates, units: Units, lang: Option<&str>) -> Result<Report>;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ProviderKind {
+  OpenWeatherMap,
+  OpenMeteo
+}
+
+impl ProviderKind {
+  /// builds the concrete provider for this kind. `api_key` is required for
+  /// `OpenWeatherMap` and ignored otherwise.
+  pub fn build(&self, api_key: Option<String>) -> Result<Box<dyn WeatherProvider + Send + Sync>> {
+    match self {
+      ProviderKind::OpenWeatherMap => {
+        let api_key = api_key.ok_or_else(|| anyhow!("--api-key is required for the openweathermap provider"))?;
+        Ok(Box::new(OpenWeatherMapProvider::new(api_key)))
+      },
+      ProviderKind::OpenMeteo => Ok(Box::new(OpenMeteoProvider))
+    }
+  }
+}
+
+impl fmt::Display for ProviderKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", match self {
+      ProviderKind::OpenWeatherMap => "openweathermap",
+      ProviderKind::OpenMeteo => "open-meteo"
+    })
+  }
+}
+
+impl FromStr for ProviderKind {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.to_ascii_lowercase().as_str() {
+      "openweathermap" | "owm" => Ok(ProviderKind::OpenWeatherMap),
+      "open-meteo" | "openmeteo" => Ok(ProviderKind::OpenMeteo),
+      s => Err(anyhow!("invalid provider '{}', must be one of: openweathermap, open-meteo", s))
+    }
+  }
+}
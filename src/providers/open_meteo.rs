@@ -0,0 +1,150 @@
+use anyhow::Result;
+use reqwest::blocking::Client;
+use serde::Deserialize;
+
+use crate::Units;
+use crate::report::{Coordinates, Report, ReportMain, ReportWind, ReportCondition, ReportClouds, ReportRain, ReportSnow};
+
+use super::WeatherProvider;
+
+const OPEN_METEO_API_ENDPOINT: &str = "https://api.open-meteo.com/v1/forecast";
+
+const CURRENT_FIELDS: &str = "temperature_2m,apparent_temperature,relative_humidity_2m,\
+  pressure_msl,cloud_cover,wind_speed_10m,wind_direction_10m,weather_code,precipitation";
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoCurrent {
+  temperature_2m: f32,
+  apparent_temperature: f32,
+  relative_humidity_2m: f32,
+  // sea-level pressure, to stay comparable with OWM's `main.pressure`
+  pressure_msl: f32,
+  cloud_cover: f32,
+  wind_speed_10m: f32,
+  wind_direction_10m: u32,
+  weather_code: u32,
+  precipitation: f32
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoResponse {
+  current: OpenMeteoCurrent
+}
+
+/// a keyless weather provider backed by Open-Meteo (https://open-meteo.com).
+pub struct OpenMeteoProvider;
+
+impl OpenMeteoProvider {
+  /// Open-Meteo has no native kelvin unit, so kelvin is derived from celsius
+  fn units_query(units: Units) -> Vec<(String, String)> {
+    match units {
+      Units::Imperial => vec![
+        ("temperature_unit".into(), "fahrenheit".into()),
+        ("wind_speed_unit".into(), "mph".into())
+      ],
+      // open-meteo defaults to km/h; m/s matches `Units::units_speed()` for
+      // metric/kelvin
+      Units::Metric | Units::Kelvin => vec![
+        ("wind_speed_unit".into(), "ms".into())
+      ]
+    }
+  }
+
+  /// maps an Open-Meteo WMO weather code to a short description, matching
+  /// the register (if not the vocabulary) of OWM's `weather[].description`
+  fn condition_description(code: u32) -> &'static str {
+    match code {
+      0 => "clear sky",
+      1 | 2 => "partly cloudy",
+      3 => "overcast clouds",
+      45 | 48 => "fog",
+      51 | 53 | 55 => "drizzle",
+      56 | 57 => "freezing drizzle",
+      61 | 63 | 65 => "rain",
+      66 | 67 => "freezing rain",
+      71 | 73 | 75 | 77 => "snow",
+      80 | 81 | 82 => "rain showers",
+      85 | 86 => "snow showers",
+      95 => "thunderstorm",
+      96 | 99 => "thunderstorm with hail",
+      _ => "unknown"
+    }
+  }
+
+  /// maps an Open-Meteo WMO weather code to the closest OWM condition id, so
+  /// `owm_condition_id` stays comparable across providers
+  fn condition_id(code: u32) -> u32 {
+    match code {
+      0 => 800,
+      1 => 801,
+      2 => 802,
+      3 => 804,
+      45 | 48 => 741,
+      51 | 53 | 55 => 300,
+      56 | 57 => 511,
+      61 | 63 | 65 => 500,
+      66 | 67 => 511,
+      71 | 73 | 75 | 77 => 600,
+      80 | 81 | 82 => 520,
+      85 | 86 => 620,
+      95 => 200,
+      96 | 99 => 202,
+      _ => 800
+    }
+  }
+}
+
+impl WeatherProvider for OpenMeteoProvider {
+  fn fetch(&self, client: &Client, coords: &Coordinates, units: Units, _lang: Option<&str>) -> Result<Report> {
+    let mut query: Vec<(String, String)> = vec![
+      ("latitude".into(), coords.lat.to_string()),
+      ("longitude".into(), coords.lon.to_string()),
+      ("current".into(), CURRENT_FIELDS.to_string())
+    ];
+    query.extend(Self::units_query(units));
+
+    let response = client.get(OPEN_METEO_API_ENDPOINT)
+      .query(&query)
+      .send()?
+      .error_for_status()?
+      .json::<OpenMeteoResponse>()?;
+
+    let current = response.current;
+
+    // open-meteo always reports celsius/fahrenheit; kelvin is derived here
+    let (temp, feels_like) = match units {
+      Units::Kelvin => (current.temperature_2m + 273.15, current.apparent_temperature + 273.15),
+      Units::Metric | Units::Imperial => (current.temperature_2m, current.apparent_temperature)
+    };
+
+    Ok(Report {
+      coord: coords.clone(),
+      weather: vec![ReportCondition {
+        id: Self::condition_id(current.weather_code),
+        main: Self::condition_description(current.weather_code).to_string(),
+        description: Self::condition_description(current.weather_code).to_string(),
+        icon: String::new()
+      }],
+      main: ReportMain {
+        temp,
+        feels_like,
+        // open-meteo's `current` block has no min/max; mirror the instant
+        temp_min: temp,
+        temp_max: temp,
+        pressure: current.pressure_msl,
+        humidity: current.relative_humidity_2m
+      },
+      wind: ReportWind {
+        speed: current.wind_speed_10m,
+        deg: current.wind_direction_10m
+      },
+      rain: ReportRain {
+        volume_1h: if current.precipitation > 0.0 { Some(current.precipitation) } else { None },
+        volume_3h: None
+      },
+      snow: ReportSnow::default(),
+      clouds: ReportClouds { all: current.cloud_cover as u32 },
+      visibility: None
+    })
+  }
+}
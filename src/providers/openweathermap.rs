@@ -0,0 +1,45 @@
+use anyhow::Result;
+use reqwest::blocking::Client;
+
+use crate::Units;
+use crate::report::{Coordinates, Report};
+
+use super::WeatherProvider;
+
+const OWM_API_ENDPOINT: &str = "https://api.openweathermap.org/data/2.5/weather";
+
+pub struct OpenWeatherMapProvider {
+  api_key: String
+}
+
+impl OpenWeatherMapProvider {
+  pub fn new(api_key: String) -> Self {
+    OpenWeatherMapProvider { api_key }
+  }
+}
+
+impl WeatherProvider for OpenWeatherMapProvider {
+  fn fetch(&self, client: &Client, coords: &Coordinates, units: Units, lang: Option<&str>) -> Result<Report> {
+    let mut query: Vec<(String, String)> = vec![
+      ("lat".into(), coords.lat.to_string()),
+      ("lon".into(), coords.lon.to_string()),
+      ("appid".into(), self.api_key.clone())
+    ];
+
+    if let Some(unit) = units.api_param() {
+      query.push(("units".into(), unit.to_string()));
+    }
+
+    if let Some(lang) = lang {
+      query.push(("lang".into(), lang.to_string()));
+    }
+
+    let report = client.get(OWM_API_ENDPOINT)
+      .query(&query)
+      .send()?
+      .error_for_status()?
+      .json::<Report>()?;
+
+    Ok(report)
+  }
+}
@@ -0,0 +1,88 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Coordinates {
+  pub lat: f32,
+  pub lon: f32
+}
+
+impl FromStr for Coordinates {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut iter = s.splitn(2, ',');
+    let lat = iter.next()
+      .and_then(|s| s.parse::<f32>().ok())
+      .ok_or_else(|| anyhow!("invalid lat"))?;
+    let lon = iter.next()
+      .and_then(|s| s.parse::<f32>().ok())
+      .ok_or_else(|| anyhow!("invalid lon"))?;
+
+    Ok(Coordinates { lat, lon })
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportCondition {
+  pub id: u32,
+  pub main: String,
+  pub description: String,
+  pub icon: String
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportMain {
+  pub temp: f32,
+  pub feels_like: f32,
+  pub temp_min: f32,
+  pub temp_max: f32,
+  pub pressure: f32,
+  pub humidity: f32
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportWind {
+  pub speed: f32,
+  pub deg: u32
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ReportRain {
+  pub volume_1h: Option<f32>,
+  pub volume_3h: Option<f32>
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ReportSnow {
+  pub volume_1h: Option<f32>,
+  pub volume_3h: Option<f32>
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportClouds {
+  pub all: u32
+}
+
+/// A weather report, normalized to OpenWeatherMap's current-weather response
+/// shape regardless of which `WeatherProvider` produced it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Report {
+  pub coord: Coordinates,
+  pub weather: Vec<ReportCondition>,
+  pub main: ReportMain,
+
+  pub wind: ReportWind,
+
+  #[serde(default)]
+  pub rain: ReportRain,
+
+  #[serde(default)]
+  pub snow: ReportSnow,
+  pub clouds: ReportClouds,
+
+  /// visibility in meters (does not honor units param)
+  pub visibility: Option<usize>
+}